@@ -0,0 +1,146 @@
+use std::{ptr, slice};
+
+use windows::{
+    Foundation::TimeSpan,
+    Win32::{
+        Media::Audio::{
+            eConsole, eRender, IAudioCaptureClient, IAudioClient, IMMDeviceEnumerator,
+            MMDeviceEnumerator, AUDCLNT_BUFFERFLAGS_SILENT, AUDCLNT_SHAREMODE_SHARED,
+            AUDCLNT_STREAMFLAGS_LOOPBACK,
+        },
+        System::Com::{CoCreateInstance, CoTaskMemFree, CLSCTX_ALL},
+    },
+};
+
+#[derive(thiserror::Error, Eq, PartialEq, Clone, Debug)]
+pub enum Error {
+    #[error("Windows API error: {0}")]
+    WindowsError(#[from] windows::core::Error),
+}
+
+/// Captures system/loopback audio (i.e. "what you hear") from the default audio
+/// render endpoint using WASAPI in loopback mode.
+pub struct LoopbackCapture {
+    client: IAudioClient,
+    capture_client: IAudioCaptureClient,
+    channel_count: u32,
+    bits_per_sample: u32,
+}
+
+unsafe impl Send for LoopbackCapture {}
+
+impl LoopbackCapture {
+    /// Opens the default render endpoint in loopback mode and starts capturing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::WindowsError` if any step of acquiring or initializing the audio
+    /// client fails, for example if there's no default render endpoint.
+    pub fn new() -> Result<Self, Error> {
+        let enumerator: IMMDeviceEnumerator =
+            unsafe { CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)? };
+        let device = unsafe { enumerator.GetDefaultAudioEndpoint(eRender, eConsole)? };
+        let client: IAudioClient = unsafe { device.Activate(CLSCTX_ALL, None)? };
+
+        let format = unsafe { client.GetMixFormat()? };
+        let channel_count = u32::from(unsafe { (*format).nChannels });
+        let bits_per_sample = u32::from(unsafe { (*format).wBitsPerSample });
+
+        let init_result = unsafe {
+            client.Initialize(
+                AUDCLNT_SHAREMODE_SHARED,
+                AUDCLNT_STREAMFLAGS_LOOPBACK,
+                0,
+                0,
+                format,
+                None,
+            )
+        };
+        unsafe { CoTaskMemFree(Some(format.cast())) };
+        init_result?;
+
+        let capture_client = unsafe { client.GetService::<IAudioCaptureClient>()? };
+
+        unsafe { client.Start()? };
+
+        Ok(Self {
+            client,
+            capture_client,
+            channel_count,
+            bits_per_sample,
+        })
+    }
+
+    /// Get the number of channels in the captured PCM stream.
+    #[must_use]
+    #[inline]
+    pub const fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    /// Get the number of bits per sample in the captured PCM stream.
+    #[must_use]
+    #[inline]
+    pub const fn bits_per_sample(&self) -> u32 {
+        self.bits_per_sample
+    }
+
+    /// Pulls the next available packet of captured audio, if any, appending it to
+    /// `out` and returning the number of bytes written along with the packet's
+    /// device position, converted from WASAPI's `pu64QPCPosition` (already in
+    /// 100ns units, the same QPC-derived clock domain `Direct3D11CaptureFrame::
+    /// SystemRelativeTime` uses) so audio and video timestamps share one origin.
+    /// Returns `(0, _)` if no packet is ready yet; callers should poll this at a
+    /// regular interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::WindowsError` if the underlying WASAPI calls fail.
+    pub fn read_packet(&self, out: &mut Vec<u8>) -> Result<(usize, TimeSpan), Error> {
+        out.clear();
+
+        let packet_size = unsafe { self.capture_client.GetNextPacketSize()? };
+        if packet_size == 0 {
+            return Ok((0, TimeSpan::default()));
+        }
+
+        let mut data = ptr::null_mut();
+        let mut frames = 0u32;
+        let mut flags = 0u32;
+        let mut qpc_position = 0u64;
+        unsafe {
+            self.capture_client.GetBuffer(
+                &mut data,
+                &mut frames,
+                &mut flags,
+                None,
+                Some(&mut qpc_position),
+            )?;
+        }
+
+        let bytes_per_frame = (self.bits_per_sample / 8) * self.channel_count;
+        let len = frames as usize * bytes_per_frame as usize;
+
+        if flags & AUDCLNT_BUFFERFLAGS_SILENT.0 as u32 != 0 {
+            out.resize(len, 0);
+        } else {
+            out.extend_from_slice(unsafe { slice::from_raw_parts(data, len) });
+        }
+
+        unsafe { self.capture_client.ReleaseBuffer(frames)? };
+
+        let timespan = TimeSpan {
+            Duration: i64::try_from(qpc_position).unwrap_or(i64::MAX),
+        };
+
+        Ok((len, timespan))
+    }
+}
+
+impl Drop for LoopbackCapture {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.client.Stop();
+        };
+    }
+}
@@ -0,0 +1,864 @@
+use std::{
+    mem,
+    os::windows::prelude::AsRawHandle,
+    sync::{
+        atomic::{self, AtomicBool},
+        mpsc, Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use windows::{
+    Foundation::AsyncActionCompletedHandler,
+    Graphics::Capture::GraphicsCaptureItem,
+    Win32::{
+        Foundation::{HANDLE, LPARAM, RPC_E_CHANGED_MODE, WPARAM},
+        Graphics::Direct3D11::{ID3D11Device, ID3D11DeviceContext},
+        System::{
+            Threading::{GetCurrentThreadId, GetThreadId},
+            WinRT::{
+                CreateDispatcherQueueController, DispatcherQueueOptions, RoInitialize,
+                RoUninitialize, DQTAT_COM_NONE, DQTYPE_THREAD_CURRENT, RO_INIT_MULTITHREADED,
+            },
+        },
+        UI::WindowsAndMessaging::{
+            DispatchMessageW, GetMessageW, PostQuitMessage, PostThreadMessageW, TranslateMessage,
+            MSG, WM_QUIT,
+        },
+    },
+};
+
+use crate::{
+    audio::LoopbackCapture,
+    d3d11::{self, create_d3d_device},
+    frame::{AudioFrame, Frame},
+    graphics_capture_api::{self, GraphicsCaptureApi, InternalCaptureControl},
+    settings::{AudioCaptureMode, Settings, WinRtInitMode},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum CaptureControlError<E> {
+    #[error("Failed to join thread")]
+    FailedToJoinThread,
+    #[error("Thread handle is taken out of struct")]
+    ThreadHandleIsTaken,
+    #[error("Failed to post thread message")]
+    FailedToPostThreadMessage,
+    #[error("Stopped handler error: {0}")]
+    StoppedHandlerError(E),
+    #[error("Windows capture error: {0}")]
+    GraphicsCaptureApiError(#[from] GraphicsCaptureApiError<E>),
+}
+
+/// Used to control the capture session
+pub struct CaptureControl<T: GraphicsCaptureApiHandler + Send + 'static, E> {
+    thread_handle: Option<JoinHandle<Result<(), GraphicsCaptureApiError<E>>>>,
+    halt_handle: Arc<AtomicBool>,
+    paused_handle: Arc<AtomicBool>,
+    callback: Arc<Mutex<T>>,
+}
+
+impl<T: GraphicsCaptureApiHandler + Send + 'static, E> CaptureControl<T, E> {
+    /// Creates a new Capture Control struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `thread_handle` - The join handle for the capture thread.
+    /// * `halt_handle` - The atomic boolean used to stop the capture thread.
+    /// * `paused_handle` - The atomic boolean used to pause/resume frame delivery.
+    /// * `callback` - The mutex-protected callback struct used to call struct methods directly.
+    ///
+    /// # Returns
+    ///
+    /// The newly created CaptureControl struct.
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        thread_handle: JoinHandle<Result<(), GraphicsCaptureApiError<E>>>,
+        halt_handle: Arc<AtomicBool>,
+        paused_handle: Arc<AtomicBool>,
+        callback: Arc<Mutex<T>>,
+    ) -> Self {
+        Self {
+            thread_handle: Some(thread_handle),
+            halt_handle,
+            paused_handle,
+            callback,
+        }
+    }
+
+    /// Checks to see if the capture thread is finished.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the capture thread is finished, `false` otherwise.
+    #[must_use]
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.thread_handle
+            .as_ref()
+            .map_or(true, std::thread::JoinHandle::is_finished)
+    }
+
+    /// Gets the join handle for the capture thread.
+    ///
+    /// # Returns
+    ///
+    /// The join handle for the capture thread.
+    #[must_use]
+    #[inline]
+    pub fn into_thread_handle(self) -> JoinHandle<Result<(), GraphicsCaptureApiError<E>>> {
+        self.thread_handle.unwrap()
+    }
+
+    /// Gets the halt handle used to pause the capture thread.
+    ///
+    /// # Returns
+    ///
+    /// The halt handle used to pause the capture thread.
+    #[must_use]
+    #[inline]
+    pub fn halt_handle(&self) -> Arc<AtomicBool> {
+        self.halt_handle.clone()
+    }
+
+    /// Gets the callback struct used to call struct methods directly.
+    ///
+    /// # Returns
+    ///
+    /// The callback struct used to call struct methods directly.
+    #[must_use]
+    #[inline]
+    pub fn callback(&self) -> Arc<Mutex<T>> {
+        self.callback.clone()
+    }
+
+    /// Pauses frame delivery. The time spent paused is held out of the media timeline, so
+    /// resuming later leaves no gap in the recorded output.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the capture was paused successfully, an error otherwise.
+    #[inline]
+    pub fn pause(&self) -> Result<(), CaptureControlError<E>> {
+        self.paused_handle.store(true, atomic::Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Resumes frame delivery after a previous call to `pause`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the capture was resumed successfully, an error otherwise.
+    #[inline]
+    pub fn resume(&self) -> Result<(), CaptureControlError<E>> {
+        self.paused_handle.store(false, atomic::Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Waits until the capturing thread stops.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the capturing thread stops successfully, an error otherwise.
+    #[inline]
+    pub fn wait(mut self) -> Result<(), CaptureControlError<E>> {
+        if let Some(thread_handle) = self.thread_handle.take() {
+            match thread_handle.join() {
+                Ok(result) => result?,
+                Err(_) => {
+                    return Err(CaptureControlError::FailedToJoinThread);
+                }
+            }
+        } else {
+            return Err(CaptureControlError::ThreadHandleIsTaken);
+        }
+
+        Ok(())
+    }
+
+    /// Gracefully stops the capture thread.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the capture thread stops successfully, an error otherwise.
+    #[inline]
+    pub fn stop(mut self) -> Result<(), CaptureControlError<E>> {
+        self.halt_handle.store(true, atomic::Ordering::Relaxed);
+
+        if let Some(thread_handle) = self.thread_handle.take() {
+            let handle = thread_handle.as_raw_handle();
+            let handle = HANDLE(handle);
+            let therad_id = unsafe { GetThreadId(handle) };
+
+            loop {
+                match unsafe {
+                    PostThreadMessageW(therad_id, WM_QUIT, WPARAM::default(), LPARAM::default())
+                } {
+                    Ok(()) => break,
+                    Err(e) => {
+                        if thread_handle.is_finished() {
+                            break;
+                        }
+
+                        if e.code().0 != -2_147_023_452 {
+                            Err(e).map_err(|_| CaptureControlError::FailedToPostThreadMessage)?;
+                        }
+                    }
+                }
+            }
+
+            match thread_handle.join() {
+                Ok(result) => result?,
+                Err(_) => {
+                    return Err(CaptureControlError::FailedToJoinThread);
+                }
+            }
+        } else {
+            return Err(CaptureControlError::ThreadHandleIsTaken);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Eq, PartialEq, Clone, Debug)]
+pub enum GraphicsCaptureApiError<E> {
+    #[error("Failed to join thread")]
+    FailedToJoinThread,
+    #[error("Failed to initialize WinRT")]
+    FailedToInitWinRT,
+    #[error("Failed to create dispatcher queue controller")]
+    FailedToCreateDispatcherQueueController,
+    #[error("Failed to shutdown dispatcher queue")]
+    FailedToShutdownDispatcherQueue,
+    #[error("Failed to set dispatcher queue completed handler")]
+    FailedToSetDispatcherQueueCompletedHandler,
+    #[error("Failed to convert item to GraphicsCaptureItem")]
+    ItemConvertFailed,
+    #[error("DirectX error: {0}")]
+    DirectXError(#[from] d3d11::Error),
+    #[error("Graphics capture error: {0}")]
+    GraphicsCaptureApiError(graphics_capture_api::Error),
+    #[error("New handler error: {0}")]
+    NewHandlerError(E),
+    #[error("Frame handler error: {0}")]
+    FrameHandlerError(E),
+}
+
+/// Spawns the background thread that polls system/loopback audio and forwards it to
+/// `T::on_audio_frame`, running until `halt` is set. `halt` is the same flag the video
+/// capture session already flips on `CaptureControl::stop`/`InternalCaptureControl::stop`,
+/// so the two threads stop together without any extra coordination.
+///
+/// If no loopback endpoint is available, the thread exits immediately and the capture
+/// simply proceeds without audio, since audio is a supplement to video, not a requirement.
+fn spawn_audio_capture_thread<T: GraphicsCaptureApiHandler + Send + 'static>(
+    callback: Arc<Mutex<T>>,
+    halt: Arc<AtomicBool>,
+    result: Arc<Mutex<Option<T::Error>>>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let Ok(capture) = LoopbackCapture::new() else {
+            return;
+        };
+
+        let mut buffer = Vec::new();
+
+        while !halt.load(atomic::Ordering::Relaxed) {
+            match capture.read_packet(&mut buffer) {
+                Ok((0, _)) | Err(_) => {
+                    thread::sleep(Duration::from_millis(5));
+                    continue;
+                }
+                Ok((len, timespan)) => {
+                    let mut frame = AudioFrame::new(&buffer[..len], timespan);
+
+                    if let Err(e) = callback.lock().on_audio_frame(&mut frame) {
+                        *result.lock() = Some(e);
+                        halt.store(true, atomic::Ordering::Relaxed);
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Initializes the WinRT/COM apartment on the current thread according to `mode`, returning
+/// whether this call actually initialized it (and therefore owns uninitializing it later).
+///
+/// With `WinRtInitMode::Auto`, `RPC_E_CHANGED_MODE` - the thread already has an apartment of a
+/// different type, set up by a host application - is treated as success rather than failure,
+/// since the apartment is still usable for capture; we just didn't initialize it ourselves.
+fn init_winrt_apartment(mode: WinRtInitMode) -> windows::core::Result<bool> {
+    match mode {
+        WinRtInitMode::Skip => Ok(false),
+        WinRtInitMode::Auto => match unsafe { RoInitialize(RO_INIT_MULTITHREADED) } {
+            Ok(()) => Ok(true),
+            Err(e) if e.code() == RPC_E_CHANGED_MODE => Ok(false),
+            Err(e) => Err(e),
+        },
+    }
+}
+
+/// A struct representing the context of the capture handler.
+pub struct Context<Flags> {
+    /// The flags that are gotten from the settings.
+    pub flags: Flags,
+    /// The direct3d device and context.
+    pub device: ID3D11Device,
+    /// The direct3d device context.
+    pub device_context: ID3D11DeviceContext,
+}
+
+/// A trait representing a graphics capture handler.
+pub trait GraphicsCaptureApiHandler: Sized {
+    /// The type of flags used to get the values from the settings.
+    type Flags;
+
+    /// The type of error that can occur during capture, the error will be returned from `CaptureControl` and `start` functions.
+    type Error: Send + Sync;
+
+    /// Starts the capture and takes control of the current thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The capture settings.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the capture was successful, otherwise returns an error of type `GraphicsCaptureApiError`.
+    #[inline]
+    fn start<T: TryInto<GraphicsCaptureItem>>(
+        settings: Settings<Self::Flags, T>,
+    ) -> Result<(), GraphicsCaptureApiError<Self::Error>>
+    where
+        Self: Send + 'static,
+        <Self as GraphicsCaptureApiHandler>::Flags: Send,
+    {
+        // Initialize WinRT
+        let winrt_initialized = init_winrt_apartment(settings.winrt_init_mode)
+            .map_err(|_| GraphicsCaptureApiError::FailedToInitWinRT)?;
+
+        // Create a dispatcher queue for the current thread
+        let options = DispatcherQueueOptions {
+            dwSize: u32::try_from(mem::size_of::<DispatcherQueueOptions>()).unwrap(),
+            threadType: DQTYPE_THREAD_CURRENT,
+            apartmentType: DQTAT_COM_NONE,
+        };
+        let controller = unsafe {
+            CreateDispatcherQueueController(options)
+                .map_err(|_| GraphicsCaptureApiError::FailedToCreateDispatcherQueueController)?
+        };
+
+        // Get current thread ID
+        let thread_id = unsafe { GetCurrentThreadId() };
+
+        // Create direct3d device and context
+        let (d3d_device, d3d_device_context) = create_d3d_device()?;
+
+        // Start capture
+        let result = Arc::new(Mutex::new(None));
+
+        let ctx = Context {
+            flags: settings.flags,
+            device: d3d_device.clone(),
+            device_context: d3d_device_context.clone(),
+        };
+
+        let callback = Arc::new(Mutex::new(
+            Self::new(ctx).map_err(GraphicsCaptureApiError::NewHandlerError)?,
+        ));
+
+        let item = settings
+            .item
+            .try_into()
+            .map_err(|_| GraphicsCaptureApiError::ItemConvertFailed)?;
+
+        let mut capture = GraphicsCaptureApi::new(
+            d3d_device,
+            d3d_device_context,
+            item,
+            callback.clone(),
+            settings.cursor_capture,
+            settings.draw_border,
+            settings.color_format,
+            thread_id,
+            result.clone(),
+        )
+        .map_err(GraphicsCaptureApiError::GraphicsCaptureApiError)?;
+        capture
+            .start_capture()
+            .map_err(GraphicsCaptureApiError::GraphicsCaptureApiError)?;
+
+        let audio_thread = (settings.audio_capture == AudioCaptureMode::Owned)
+            .then(|| spawn_audio_capture_thread(callback, capture.halt_handle(), result.clone()));
+
+        // Message loop
+        let mut message = MSG::default();
+        unsafe {
+            while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                let _ = TranslateMessage(&message);
+                DispatchMessageW(&message);
+            }
+        }
+
+        // Shutdown dispatcher queue
+        let async_action = controller
+            .ShutdownQueueAsync()
+            .map_err(|_| GraphicsCaptureApiError::FailedToShutdownDispatcherQueue)?;
+        async_action
+            .SetCompleted(&AsyncActionCompletedHandler::new(
+                move |_, _| -> Result<(), windows::core::Error> {
+                    unsafe { PostQuitMessage(0) };
+                    Ok(())
+                },
+            ))
+            .map_err(|_| GraphicsCaptureApiError::FailedToSetDispatcherQueueCompletedHandler)?;
+
+        // Final message loop
+        let mut message = MSG::default();
+        unsafe {
+            while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                let _ = TranslateMessage(&message);
+                DispatchMessageW(&message);
+            }
+        }
+
+        // Stop capture
+        capture.stop_capture();
+
+        // Stop the audio capture thread, if this session owns one
+        if let Some(audio_thread) = audio_thread {
+            audio_thread
+                .join()
+                .expect("Failed to join audio capture thread");
+        }
+
+        // Uninitialize WinRT, but only if this call is the one that initialized it
+        if winrt_initialized {
+            unsafe { RoUninitialize() };
+        }
+
+        // Check handler result
+        let result = result.lock().take();
+        if let Some(e) = result {
+            return Err(GraphicsCaptureApiError::FrameHandlerError(e));
+        }
+
+        Ok(())
+    }
+
+    /// Starts the capture without taking control of the current thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The capture settings.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(CaptureControl)` if the capture was successful, otherwise returns an error of type `GraphicsCaptureApiError`.
+    #[inline]
+    fn start_free_threaded<T: TryInto<GraphicsCaptureItem> + Send + 'static>(
+        settings: Settings<Self::Flags, T>,
+    ) -> Result<CaptureControl<Self, Self::Error>, GraphicsCaptureApiError<Self::Error>>
+    where
+        Self: Send + 'static,
+        <Self as GraphicsCaptureApiHandler>::Flags: Send,
+    {
+        let (halt_sender, halt_receiver) = mpsc::channel::<Arc<AtomicBool>>();
+        let (paused_sender, paused_receiver) = mpsc::channel::<Arc<AtomicBool>>();
+        let (callback_sender, callback_receiver) = mpsc::channel::<Arc<Mutex<Self>>>();
+
+        let thread_handle = thread::spawn(
+            move || -> Result<(), GraphicsCaptureApiError<Self::Error>> {
+                // Initialize WinRT
+                let winrt_initialized = init_winrt_apartment(settings.winrt_init_mode)
+                    .map_err(|_| GraphicsCaptureApiError::FailedToInitWinRT)?;
+
+                // Create a dispatcher queue for the current thread
+                let options = DispatcherQueueOptions {
+                    dwSize: u32::try_from(mem::size_of::<DispatcherQueueOptions>()).unwrap(),
+                    threadType: DQTYPE_THREAD_CURRENT,
+                    apartmentType: DQTAT_COM_NONE,
+                };
+                let controller = unsafe {
+                    CreateDispatcherQueueController(options).map_err(|_| {
+                        GraphicsCaptureApiError::FailedToCreateDispatcherQueueController
+                    })?
+                };
+
+                // Get current thread ID
+                let thread_id = unsafe { GetCurrentThreadId() };
+
+                // Create direct3d device and context
+                let (d3d_device, d3d_device_context) = create_d3d_device()?;
+
+                // Start capture
+                let result = Arc::new(Mutex::new(None));
+
+                let ctx = Context {
+                    flags: settings.flags,
+                    device: d3d_device.clone(),
+                    device_context: d3d_device_context.clone(),
+                };
+
+                let callback = Arc::new(Mutex::new(
+                    Self::new(ctx).map_err(GraphicsCaptureApiError::NewHandlerError)?,
+                ));
+
+                let item = settings
+                    .item
+                    .try_into()
+                    .map_err(|_| GraphicsCaptureApiError::ItemConvertFailed)?;
+
+                let mut capture = GraphicsCaptureApi::new(
+                    d3d_device,
+                    d3d_device_context,
+                    item,
+                    callback.clone(),
+                    settings.cursor_capture,
+                    settings.draw_border,
+                    settings.color_format,
+                    thread_id,
+                    result.clone(),
+                )
+                .map_err(GraphicsCaptureApiError::GraphicsCaptureApiError)?;
+                capture
+                    .start_capture()
+                    .map_err(GraphicsCaptureApiError::GraphicsCaptureApiError)?;
+
+                let audio_thread = (settings.audio_capture == AudioCaptureMode::Owned).then(|| {
+                    spawn_audio_capture_thread(
+                        callback.clone(),
+                        capture.halt_handle(),
+                        result.clone(),
+                    )
+                });
+
+                // Send halt handle
+                let halt_handle = capture.halt_handle();
+                halt_sender.send(halt_handle).unwrap();
+
+                // Send pause handle
+                let paused_handle = capture.pause_handle();
+                paused_sender.send(paused_handle).unwrap();
+
+                // Send callback
+                callback_sender.send(callback).unwrap();
+
+                // Message loop
+                let mut message = MSG::default();
+                unsafe {
+                    while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                        let _ = TranslateMessage(&message);
+                        DispatchMessageW(&message);
+                    }
+                }
+
+                // Shutdown dispatcher queue
+                let async_action = controller
+                    .ShutdownQueueAsync()
+                    .map_err(|_| GraphicsCaptureApiError::FailedToShutdownDispatcherQueue)?;
+
+                async_action
+                    .SetCompleted(&AsyncActionCompletedHandler::new(
+                        move |_, _| -> Result<(), windows::core::Error> {
+                            unsafe { PostQuitMessage(0) };
+                            Ok(())
+                        },
+                    ))
+                    .map_err(|_| {
+                        GraphicsCaptureApiError::FailedToSetDispatcherQueueCompletedHandler
+                    })?;
+
+                // Final message loop
+                let mut message = MSG::default();
+                unsafe {
+                    while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                        let _ = TranslateMessage(&message);
+                        DispatchMessageW(&message);
+                    }
+                }
+
+                // Stop capture
+                capture.stop_capture();
+
+                // Stop the audio capture thread, if this session owns one
+                if let Some(audio_thread) = audio_thread {
+                    audio_thread
+                        .join()
+                        .expect("Failed to join audio capture thread");
+                }
+
+                // Uninitialize WinRT, but only if this call is the one that initialized it
+                if winrt_initialized {
+                    unsafe { RoUninitialize() };
+                }
+
+                // Check handler result
+                let result = result.lock().take();
+                if let Some(e) = result {
+                    return Err(GraphicsCaptureApiError::FrameHandlerError(e));
+                }
+
+                Ok(())
+            },
+        );
+
+        let Ok(halt_handle) = halt_receiver.recv() else {
+            match thread_handle.join() {
+                Ok(result) => return Err(result.err().unwrap()),
+                Err(_) => {
+                    return Err(GraphicsCaptureApiError::FailedToJoinThread);
+                }
+            }
+        };
+
+        let Ok(paused_handle) = paused_receiver.recv() else {
+            match thread_handle.join() {
+                Ok(result) => return Err(result.err().unwrap()),
+                Err(_) => {
+                    return Err(GraphicsCaptureApiError::FailedToJoinThread);
+                }
+            }
+        };
+
+        let Ok(callback) = callback_receiver.recv() else {
+            match thread_handle.join() {
+                Ok(result) => return Err(result.err().unwrap()),
+                Err(_) => {
+                    return Err(GraphicsCaptureApiError::FailedToJoinThread);
+                }
+            }
+        };
+
+        Ok(CaptureControl::new(
+            thread_handle,
+            halt_handle,
+            paused_handle,
+            callback,
+        ))
+    }
+
+    /// Function that will be called to create the struct. The flags can be passed from settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - The flags used to create the struct.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Self)` if the struct creation was successful, otherwise returns an error of type `Self::Error`.
+    fn new(ctx: Context<Self::Flags>) -> Result<Self, Self::Error>;
+
+    /// Called every time a new frame is available.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - A mutable reference to the captured frame.
+    /// * `capture_control` - The internal capture control.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the frame processing was successful, otherwise returns an error of type `Self::Error`.
+    fn on_frame_arrived(
+        &mut self,
+        frame: &mut Frame,
+        capture_control: InternalCaptureControl,
+    ) -> Result<(), Self::Error>;
+
+    /// Optional handler called when the capture item (usually a window) closes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the handler execution was successful, otherwise returns an error of type `Self::Error`.
+    #[inline]
+    fn on_closed(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Optional handler called every time a new block of system/loopback audio is
+    /// available. Does nothing by default, so handlers that don't care about audio
+    /// don't have to implement it.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - A mutable reference to the captured audio frame.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the frame processing was successful, otherwise returns an error of type `Self::Error`.
+    #[inline]
+    fn on_audio_frame(&mut self, _frame: &mut AudioFrame) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Drives several simultaneous capture sessions (e.g. one per monitor, for recording every
+/// connected display at once) as a single recording, instead of each running as a fully
+/// independent capture: every session is started with `AudioCaptureMode::Shared`, so only
+/// one WASAPI loopback thread runs for the whole group, fanning each captured block out to
+/// every session's `on_audio_frame`; and every session is paused/resumed/stopped together
+/// instead of one at a time.
+///
+/// The Windows Graphics Capture API requires a dedicated `Direct3D11CaptureFramePool` per
+/// captured item, so `MultiSource` still runs one `start_free_threaded` session per item -
+/// there's no way to unify video capture itself across monitors - but the loopback audio and
+/// the pause/resume/stop timeline are genuinely shared.
+pub struct MultiSource<T: GraphicsCaptureApiHandler + Send + 'static> {
+    controls: Vec<CaptureControl<T, T::Error>>,
+    audio_halt: Arc<AtomicBool>,
+    audio_thread: Option<JoinHandle<()>>,
+    audio_error: Arc<Mutex<Option<T::Error>>>,
+}
+
+impl<T: GraphicsCaptureApiHandler + Send + 'static> MultiSource<T> {
+    /// Starts one free-threaded capture session per item in `settings`, plus a single
+    /// shared loopback audio thread feeding every session's `on_audio_frame`.
+    ///
+    /// Each `Settings` should already carry `AudioCaptureMode::Shared` (e.g. via
+    /// `Settings::with_audio_capture`) - `MultiSource` starts sessions as given rather than
+    /// overriding their audio mode, so a session can still opt to capture its own audio
+    /// alongside the shared thread if that's ever wanted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first session's error if any of them fails to start. Since the `Err`
+    /// variant only carries that session's error, not the others' `CaptureControl`s, any
+    /// sessions that did start successfully before the failing one are stopped here rather
+    /// than left running with no handle left for the caller to stop them by.
+    pub fn start<I: TryInto<GraphicsCaptureItem> + Send + 'static>(
+        settings: Vec<Settings<T::Flags, I>>,
+    ) -> Result<Self, GraphicsCaptureApiError<T::Error>>
+    where
+        T::Flags: Send,
+    {
+        let mut controls = Vec::with_capacity(settings.len());
+        for settings in settings {
+            match T::start_free_threaded(settings) {
+                Ok(control) => controls.push(control),
+                Err(e) => {
+                    for control in controls {
+                        let _ = control.stop();
+                    }
+
+                    return Err(e);
+                }
+            }
+        }
+
+        let callbacks: Vec<_> = controls.iter().map(CaptureControl::callback).collect();
+        let audio_halt = Arc::new(AtomicBool::new(false));
+        let audio_error = Arc::new(Mutex::new(None));
+
+        let audio_thread = {
+            let audio_halt = audio_halt.clone();
+            let audio_error = audio_error.clone();
+
+            thread::spawn(move || {
+                let Ok(capture) = LoopbackCapture::new() else {
+                    return;
+                };
+
+                let mut buffer = Vec::new();
+
+                while !audio_halt.load(atomic::Ordering::Relaxed) {
+                    match capture.read_packet(&mut buffer) {
+                        Ok((0, _)) | Err(_) => {
+                            thread::sleep(Duration::from_millis(5));
+                            continue;
+                        }
+                        Ok((len, timespan)) => {
+                            for callback in &callbacks {
+                                let mut frame = AudioFrame::new(&buffer[..len], timespan);
+                                if let Err(e) = callback.lock().on_audio_frame(&mut frame) {
+                                    *audio_error.lock() = Some(e);
+                                    audio_halt.store(true, atomic::Ordering::Relaxed);
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            controls,
+            audio_halt,
+            audio_thread: Some(audio_thread),
+            audio_error,
+        })
+    }
+
+    /// Pauses frame delivery on every session together.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every session was paused successfully, an error otherwise.
+    #[inline]
+    pub fn pause(&self) -> Result<(), CaptureControlError<T::Error>> {
+        for control in &self.controls {
+            control.pause()?;
+        }
+
+        Ok(())
+    }
+
+    /// Resumes frame delivery on every session together.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every session was resumed successfully, an error otherwise.
+    #[inline]
+    pub fn resume(&self) -> Result<(), CaptureControlError<T::Error>> {
+        for control in &self.controls {
+            control.resume()?;
+        }
+
+        Ok(())
+    }
+
+    /// Gracefully stops every session together, then stops the shared audio capture thread.
+    ///
+    /// Every session is stopped and the audio thread is always halted and joined, even if an
+    /// earlier session's `stop` fails, so a single misbehaving session can't leave the rest -
+    /// or the shared audio thread - running forever.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every session stopped successfully and the shared audio thread reported
+    /// no error, otherwise the first error encountered.
+    #[inline]
+    pub fn stop(mut self) -> Result<(), CaptureControlError<T::Error>> {
+        let mut first_error = None;
+        for control in self.controls.drain(..) {
+            if let Err(e) = control.stop() {
+                first_error.get_or_insert(e);
+            }
+        }
+
+        self.audio_halt.store(true, atomic::Ordering::Relaxed);
+        if let Some(audio_thread) = self.audio_thread.take() {
+            if audio_thread.join().is_err() {
+                first_error.get_or_insert(CaptureControlError::FailedToJoinThread);
+            }
+        }
+
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+
+        if let Some(e) = self.audio_error.lock().take() {
+            return Err(CaptureControlError::StoppedHandlerError(e));
+        }
+
+        Ok(())
+    }
+}
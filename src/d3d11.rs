@@ -0,0 +1,572 @@
+use windows::{
+    core::{s, Interface},
+    Graphics::DirectX::Direct3D11::{IDirect3DDevice, IDirect3DSurface},
+    Win32::{
+        Graphics::{
+            Direct3D::{
+                Fxc::D3DCompile, D3D_DRIVER_TYPE_HARDWARE, D3D_FEATURE_LEVEL,
+                D3D_FEATURE_LEVEL_10_0, D3D_FEATURE_LEVEL_10_1, D3D_FEATURE_LEVEL_11_0,
+                D3D_FEATURE_LEVEL_11_1, D3D_FEATURE_LEVEL_9_1, D3D_FEATURE_LEVEL_9_2,
+                D3D_FEATURE_LEVEL_9_3, D3D_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP,
+            },
+            Direct3D11::{
+                D3D11CreateDevice, ID3D11Buffer, ID3D11Device, ID3D11DeviceContext,
+                ID3D11InputLayout, ID3D11PixelShader, ID3D11RenderTargetView, ID3D11SamplerState,
+                ID3D11ShaderResourceView, ID3D11Texture2D, ID3D11VertexShader,
+                D3D11_BIND_RENDER_TARGET, D3D11_BIND_SHADER_RESOURCE, D3D11_BIND_VERTEX_BUFFER,
+                D3D11_BUFFER_DESC, D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                D3D11_FILTER_MIN_MAG_MIP_LINEAR, D3D11_INPUT_ELEMENT_DESC,
+                D3D11_INPUT_PER_VERTEX_DATA, D3D11_SAMPLER_DESC, D3D11_SDK_VERSION,
+                D3D11_SUBRESOURCE_DATA, D3D11_TEXTURE2D_DESC, D3D11_TEXTURE_ADDRESS_CLAMP,
+                D3D11_USAGE_DEFAULT, D3D11_VIEWPORT,
+            },
+            Dxgi::{
+                Common::{DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_FORMAT_R32G32_FLOAT, DXGI_SAMPLE_DESC},
+                IDXGIDevice, IDXGISurface,
+            },
+        },
+        System::WinRT::Direct3D11::{
+            CreateDirect3D11DeviceFromDXGIDevice, CreateDirect3D11SurfaceFromDXGISurface,
+        },
+    },
+};
+
+use crate::encoder::ScaleMode;
+
+/// Combined HLSL source for the output-scaling pass: a pass-through vertex
+/// shader plus a pixel shader that samples the source texture. Compiled at
+/// runtime so the crate doesn't need an offline shader build step.
+const SCALE_SHADER_SOURCE: &str = r"
+struct VSInput
+{
+    float2 pos : POSITION;
+    float2 uv : TEXCOORD0;
+};
+
+struct PSInput
+{
+    float4 pos : SV_POSITION;
+    float2 uv : TEXCOORD0;
+};
+
+PSInput vs_main(VSInput input)
+{
+    PSInput output;
+    output.pos = float4(input.pos, 0.0, 1.0);
+    output.uv = input.uv;
+    return output;
+}
+
+Texture2D sourceTexture : register(t0);
+SamplerState sourceSampler : register(s0);
+
+float4 ps_main(PSInput input) : SV_TARGET
+{
+    return sourceTexture.Sample(sourceSampler, input.uv);
+}
+";
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ScalerVertex {
+    pos: [f32; 2],
+    uv: [f32; 2],
+}
+
+#[derive(thiserror::Error, Eq, PartialEq, Clone, Debug)]
+pub enum Error {
+    #[error("Failed to create DirectX device with the recommended feature levels")]
+    FeatureLevelNotSatisfied,
+    #[error("Failed to compile scaling shader: {0}")]
+    ShaderCompileFailed(String),
+    #[error("Windows API Error: {0}")]
+    WindowsError(#[from] windows::core::Error),
+}
+
+/// Used To Send DirectX Device Across Threads
+pub struct SendDirectX<T>(pub T);
+
+impl<T> SendDirectX<T> {
+    /// Create A New `SendDirectX` Instance
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The DirectX Device
+    ///
+    /// # Returns
+    ///
+    /// Returns A New `SendDirectX` Instance
+    #[must_use]
+    #[inline]
+    pub const fn new(device: T) -> Self {
+        Self(device)
+    }
+}
+
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl<T> Send for SendDirectX<T> {}
+
+/// Create `ID3D11Device` and `ID3D11DeviceContext`
+#[inline]
+pub fn create_d3d_device() -> Result<(ID3D11Device, ID3D11DeviceContext), Error> {
+    // Array of Direct3D feature levels.
+    // The feature levels are listed in descending order of capability.
+    // The highest feature level supported by the system is at index 0.
+    // The lowest feature level supported by the system is at the last index.
+    let feature_flags = [
+        D3D_FEATURE_LEVEL_11_1,
+        D3D_FEATURE_LEVEL_11_0,
+        D3D_FEATURE_LEVEL_10_1,
+        D3D_FEATURE_LEVEL_10_0,
+        D3D_FEATURE_LEVEL_9_3,
+        D3D_FEATURE_LEVEL_9_2,
+        D3D_FEATURE_LEVEL_9_1,
+    ];
+
+    let mut d3d_device = None;
+    let mut feature_level = D3D_FEATURE_LEVEL::default();
+    let mut d3d_device_context = None;
+    unsafe {
+        D3D11CreateDevice(
+            None,
+            D3D_DRIVER_TYPE_HARDWARE,
+            None,
+            D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+            Some(&feature_flags),
+            D3D11_SDK_VERSION,
+            Some(&mut d3d_device),
+            Some(&mut feature_level),
+            Some(&mut d3d_device_context),
+        )?;
+    };
+
+    if feature_level.0 < D3D_FEATURE_LEVEL_11_0.0 {
+        return Err(Error::FeatureLevelNotSatisfied);
+    }
+
+    Ok((d3d_device.unwrap(), d3d_device_context.unwrap()))
+}
+
+/// Create `IDirect3DDevice` From `ID3D11Device`
+#[inline]
+pub fn create_direct3d_device(d3d_device: &ID3D11Device) -> Result<IDirect3DDevice, Error> {
+    let dxgi_device: IDXGIDevice = d3d_device.cast()?;
+    let inspectable = unsafe { CreateDirect3D11DeviceFromDXGIDevice(&dxgi_device)? };
+    let device: IDirect3DDevice = inspectable.cast()?;
+
+    Ok(device)
+}
+
+/// Create `IDirect3DSurface` From `ID3D11Texture2D`, so it can be handed to
+/// WinRT APIs (such as `MediaStreamSample::CreateFromDirect3D11Surface`)
+/// that only accept the WinRT surface wrapper.
+#[inline]
+fn create_direct3d_surface(texture: &ID3D11Texture2D) -> Result<IDirect3DSurface, Error> {
+    let dxgi_surface: IDXGISurface = texture.cast()?;
+    let inspectable = unsafe { CreateDirect3D11SurfaceFromDXGISurface(&dxgi_surface)? };
+    let surface: IDirect3DSurface = inspectable.cast()?;
+
+    Ok(surface)
+}
+
+/// GPU-copy `source` into a brand-new texture of the same description and
+/// wrap it as an `IDirect3DSurface`. Frames are now handed off to a queue
+/// drained by a separate encoder thread (see `EncoderQueue`), so a queued
+/// job can no longer just hold a reference to the capture frame pool's
+/// texture: the pool recycles that texture (it's created with a single
+/// buffer) the moment `on_frame_arrived` returns, well before the encoder
+/// thread gets around to the job. Copying into an owned texture here gives
+/// every queued job its own backing memory instead of aliasing the pool.
+#[inline]
+pub fn copy_to_owned_surface(
+    device: &ID3D11Device,
+    context: &ID3D11DeviceContext,
+    source: &ID3D11Texture2D,
+) -> Result<IDirect3DSurface, Error> {
+    let mut desc = D3D11_TEXTURE2D_DESC::default();
+    unsafe { source.GetDesc(&mut desc) };
+
+    let mut owned_texture = None;
+    unsafe { device.CreateTexture2D(&desc, None, Some(&mut owned_texture))? };
+    let owned_texture = owned_texture.unwrap();
+
+    unsafe { context.CopyResource(&owned_texture, source) };
+
+    create_direct3d_surface(&owned_texture)
+}
+
+/// Renders a source texture into a render target sized to the encoder's
+/// configured output resolution, applying `ScaleMode` to decide how the
+/// source rectangle maps onto the destination. Used by `VideoEncoder` to
+/// decouple the capture resolution from the output resolution without a
+/// CPU round trip.
+///
+/// `scale` allocates a fresh output texture on every call rather than
+/// rendering into one kept on `Scaler` itself: frames are now queued and
+/// consumed by a separate encoder thread, so a shared output texture would
+/// be overwritten by the next frame before the previous queued job was
+/// encoded, collapsing every in-flight frame down to whatever was rendered
+/// last.
+pub struct Scaler {
+    context: ID3D11DeviceContext,
+    vertex_shader: ID3D11VertexShader,
+    pixel_shader: ID3D11PixelShader,
+    input_layout: ID3D11InputLayout,
+    vertex_buffer: ID3D11Buffer,
+    sampler: ID3D11SamplerState,
+    output_width: u32,
+    output_height: u32,
+    viewport: D3D11_VIEWPORT,
+}
+
+impl Scaler {
+    /// Create a new `Scaler` that renders into an output texture of
+    /// `output_width` x `output_height`.
+    #[inline]
+    pub fn new(
+        device: &ID3D11Device,
+        context: ID3D11DeviceContext,
+        output_width: u32,
+        output_height: u32,
+    ) -> Result<Self, Error> {
+        let vertex_shader_blob = compile_shader(SCALE_SHADER_SOURCE, s!("vs_main"), s!("vs_5_0"))?;
+        let mut vertex_shader = None;
+        unsafe {
+            device.CreateVertexShader(
+                shader_bytecode(&vertex_shader_blob),
+                None,
+                Some(&mut vertex_shader),
+            )?;
+        };
+        let vertex_shader = vertex_shader.unwrap();
+
+        let pixel_shader_blob = compile_shader(SCALE_SHADER_SOURCE, s!("ps_main"), s!("ps_5_0"))?;
+        let mut pixel_shader = None;
+        unsafe {
+            device.CreatePixelShader(
+                shader_bytecode(&pixel_shader_blob),
+                None,
+                Some(&mut pixel_shader),
+            )?;
+        };
+        let pixel_shader = pixel_shader.unwrap();
+
+        let input_element_desc = [
+            D3D11_INPUT_ELEMENT_DESC {
+                SemanticName: s!("POSITION"),
+                SemanticIndex: 0,
+                Format: DXGI_FORMAT_R32G32_FLOAT,
+                InputSlot: 0,
+                AlignedByteOffset: 0,
+                InputSlotClass: D3D11_INPUT_PER_VERTEX_DATA,
+                InstanceDataStepRate: 0,
+            },
+            D3D11_INPUT_ELEMENT_DESC {
+                SemanticName: s!("TEXCOORD"),
+                SemanticIndex: 0,
+                Format: DXGI_FORMAT_R32G32_FLOAT,
+                InputSlot: 0,
+                AlignedByteOffset: 8,
+                InputSlotClass: D3D11_INPUT_PER_VERTEX_DATA,
+                InstanceDataStepRate: 0,
+            },
+        ];
+        let mut input_layout = None;
+        unsafe {
+            device.CreateInputLayout(
+                &input_element_desc,
+                shader_bytecode(&vertex_shader_blob),
+                Some(&mut input_layout),
+            )?;
+        };
+        let input_layout = input_layout.unwrap();
+
+        // Full-clip-space quad; texture coordinates are rewritten per frame
+        // in `update_geometry` to implement `ScaleMode::Fill` cropping.
+        let vertices = full_uv_quad();
+        let vertex_buffer_desc = D3D11_BUFFER_DESC {
+            ByteWidth: u32::try_from(vertices.len() * std::mem::size_of::<ScalerVertex>()).unwrap(),
+            Usage: D3D11_USAGE_DEFAULT,
+            BindFlags: D3D11_BIND_VERTEX_BUFFER.0 as u32,
+            ..Default::default()
+        };
+        let vertex_buffer_data = D3D11_SUBRESOURCE_DATA {
+            pSysMem: vertices.as_ptr().cast(),
+            ..Default::default()
+        };
+        let mut vertex_buffer = None;
+        unsafe {
+            device.CreateBuffer(
+                &vertex_buffer_desc,
+                Some(&vertex_buffer_data),
+                Some(&mut vertex_buffer),
+            )?;
+        };
+        let vertex_buffer = vertex_buffer.unwrap();
+
+        let sampler_desc = D3D11_SAMPLER_DESC {
+            Filter: D3D11_FILTER_MIN_MAG_MIP_LINEAR,
+            AddressU: D3D11_TEXTURE_ADDRESS_CLAMP,
+            AddressV: D3D11_TEXTURE_ADDRESS_CLAMP,
+            AddressW: D3D11_TEXTURE_ADDRESS_CLAMP,
+            ComparisonFunc: windows::Win32::Graphics::Direct3D11::D3D11_COMPARISON_NEVER,
+            MaxLOD: f32::MAX,
+            ..Default::default()
+        };
+        let mut sampler = None;
+        unsafe { device.CreateSamplerState(&sampler_desc, Some(&mut sampler))? };
+        let sampler = sampler.unwrap();
+
+        Ok(Self {
+            context,
+            vertex_shader,
+            pixel_shader,
+            input_layout,
+            vertex_buffer,
+            sampler,
+            output_width,
+            output_height,
+            viewport: D3D11_VIEWPORT::default(),
+        })
+    }
+
+    /// Scale `source_texture` (of `source_width` x `source_height`) according
+    /// to `scale_mode`, into a freshly allocated texture sized to this
+    /// `Scaler`'s configured output resolution, and return it as an owned
+    /// `IDirect3DSurface` so it can be handed to
+    /// `MediaStreamSample::CreateFromDirect3D11Surface`. A new destination
+    /// texture is allocated on every call rather than reused, since the
+    /// surface returned here is queued for a separate encoder thread to
+    /// consume later, and reusing one texture across calls would let a
+    /// later frame overwrite it first.
+    #[inline]
+    pub fn scale(
+        &mut self,
+        source_texture: &ID3D11Texture2D,
+        source_width: u32,
+        source_height: u32,
+        scale_mode: ScaleMode,
+    ) -> Result<IDirect3DSurface, Error> {
+        self.update_geometry(source_width, source_height, scale_mode);
+
+        let device: ID3D11Device = unsafe { self.context.GetDevice()? };
+
+        let output_texture_desc = D3D11_TEXTURE2D_DESC {
+            Width: self.output_width,
+            Height: self.output_height,
+            MipLevels: 1,
+            ArraySize: 1,
+            Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+            SampleDesc: DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Usage: D3D11_USAGE_DEFAULT,
+            BindFlags: (D3D11_BIND_RENDER_TARGET.0 | D3D11_BIND_SHADER_RESOURCE.0) as u32,
+            CPUAccessFlags: 0,
+            MiscFlags: 0,
+        };
+
+        let mut output_texture = None;
+        unsafe { device.CreateTexture2D(&output_texture_desc, None, Some(&mut output_texture))? };
+        let output_texture = output_texture.unwrap();
+
+        let mut render_target_view = None;
+        unsafe {
+            device.CreateRenderTargetView(&output_texture, None, Some(&mut render_target_view))?;
+        };
+        let render_target_view = render_target_view.unwrap();
+
+        let mut shader_resource_view = None;
+        unsafe {
+            device.CreateShaderResourceView(
+                source_texture,
+                None,
+                Some(&mut shader_resource_view),
+            )?;
+        };
+        let shader_resource_view = shader_resource_view.unwrap();
+
+        unsafe {
+            self.context
+                .ClearRenderTargetView(&render_target_view, &[0.0, 0.0, 0.0, 1.0]);
+            self.context
+                .OMSetRenderTargets(Some(&[Some(render_target_view.clone())]), None);
+            self.context.RSSetViewports(Some(&[self.viewport]));
+            self.context
+                .IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP);
+            self.context.IASetInputLayout(&self.input_layout);
+            let stride = u32::try_from(std::mem::size_of::<ScalerVertex>()).unwrap();
+            self.context.IASetVertexBuffers(
+                0,
+                1,
+                Some(&Some(self.vertex_buffer.clone())),
+                Some(&stride),
+                Some(&0),
+            );
+            self.context.VSSetShader(&self.vertex_shader, None);
+            self.context.PSSetShader(&self.pixel_shader, None);
+            self.context
+                .PSSetShaderResources(0, Some(&[Some(shader_resource_view)]));
+            self.context
+                .PSSetSamplers(0, Some(&[Some(self.sampler.clone())]));
+            self.context.Draw(4, 0);
+        };
+
+        create_direct3d_surface(&output_texture)
+    }
+
+    /// Recompute the viewport (for `Fit`/`Stretch`) or the vertex buffer's
+    /// texture coordinates (for `Fill`) for the current source size.
+    fn update_geometry(&mut self, source_width: u32, source_height: u32, scale_mode: ScaleMode) {
+        let (dst_w, dst_h) = (self.output_width as f32, self.output_height as f32);
+        let (src_w, src_h) = (source_width as f32, source_height as f32);
+
+        self.viewport = match scale_mode {
+            ScaleMode::Stretch => D3D11_VIEWPORT {
+                TopLeftX: 0.0,
+                TopLeftY: 0.0,
+                Width: dst_w,
+                Height: dst_h,
+                MinDepth: 0.0,
+                MaxDepth: 1.0,
+            },
+            ScaleMode::Fit => {
+                let scale = (dst_w / src_w).min(dst_h / src_h);
+                let draw_w = src_w * scale;
+                let draw_h = src_h * scale;
+                D3D11_VIEWPORT {
+                    TopLeftX: (dst_w - draw_w) / 2.0,
+                    TopLeftY: (dst_h - draw_h) / 2.0,
+                    Width: draw_w,
+                    Height: draw_h,
+                    MinDepth: 0.0,
+                    MaxDepth: 1.0,
+                }
+            }
+            ScaleMode::Fill => D3D11_VIEWPORT {
+                TopLeftX: 0.0,
+                TopLeftY: 0.0,
+                Width: dst_w,
+                Height: dst_h,
+                MinDepth: 0.0,
+                MaxDepth: 1.0,
+            },
+        };
+
+        let (u0, v0, u1, v1) = if matches!(scale_mode, ScaleMode::Fill) {
+            let src_aspect = src_w / src_h;
+            let dst_aspect = dst_w / dst_h;
+            if src_aspect > dst_aspect {
+                let crop_w = dst_aspect / src_aspect;
+                ((1.0 - crop_w) / 2.0, 0.0, (1.0 + crop_w) / 2.0, 1.0)
+            } else {
+                let crop_h = src_aspect / dst_aspect;
+                (0.0, (1.0 - crop_h) / 2.0, 1.0, (1.0 + crop_h) / 2.0)
+            }
+        } else {
+            (0.0, 0.0, 1.0, 1.0)
+        };
+
+        let vertices = [
+            ScalerVertex {
+                pos: [-1.0, 1.0],
+                uv: [u0, v0],
+            },
+            ScalerVertex {
+                pos: [-1.0, -1.0],
+                uv: [u0, v1],
+            },
+            ScalerVertex {
+                pos: [1.0, 1.0],
+                uv: [u1, v0],
+            },
+            ScalerVertex {
+                pos: [1.0, -1.0],
+                uv: [u1, v1],
+            },
+        ];
+
+        unsafe {
+            self.context.UpdateSubresource(
+                &self.vertex_buffer,
+                0,
+                None,
+                vertices.as_ptr().cast(),
+                0,
+                0,
+            );
+        };
+    }
+}
+
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl Send for Scaler {}
+
+/// Four vertices covering the full clip-space quad with unmodified (0..1)
+/// texture coordinates; overwritten per-frame by `Scaler::update_geometry`.
+fn full_uv_quad() -> [ScalerVertex; 4] {
+    [
+        ScalerVertex {
+            pos: [-1.0, 1.0],
+            uv: [0.0, 0.0],
+        },
+        ScalerVertex {
+            pos: [-1.0, -1.0],
+            uv: [0.0, 1.0],
+        },
+        ScalerVertex {
+            pos: [1.0, 1.0],
+            uv: [1.0, 0.0],
+        },
+        ScalerVertex {
+            pos: [1.0, -1.0],
+            uv: [1.0, 1.0],
+        },
+    ]
+}
+
+fn compile_shader(
+    source: &str,
+    entry_point: windows::core::PCSTR,
+    target: windows::core::PCSTR,
+) -> Result<windows::Win32::Graphics::Direct3D::ID3DBlob, Error> {
+    let mut code = None;
+    let mut errors = None;
+
+    let result = unsafe {
+        D3DCompile(
+            source.as_ptr().cast(),
+            source.len(),
+            None,
+            None,
+            None,
+            entry_point,
+            target,
+            0,
+            0,
+            &mut code,
+            Some(&mut errors),
+        )
+    };
+
+    if let Err(e) = result {
+        let message = errors.map_or_else(
+            || e.message(),
+            |errors| unsafe {
+                let ptr = errors.GetBufferPointer().cast::<u8>();
+                let len = errors.GetBufferSize();
+                String::from_utf8_lossy(std::slice::from_raw_parts(ptr, len)).into_owned()
+            },
+        );
+        return Err(Error::ShaderCompileFailed(message));
+    }
+
+    Ok(code.unwrap())
+}
+
+fn shader_bytecode(blob: &windows::Win32::Graphics::Direct3D::ID3DBlob) -> &[u8] {
+    unsafe {
+        std::slice::from_raw_parts(blob.GetBufferPointer().cast::<u8>(), blob.GetBufferSize())
+    }
+}
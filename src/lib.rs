@@ -0,0 +1,31 @@
+//! # Windows Capture Rust Library
+//!
+//! **Windows Capture** is a highly efficient Rust library that enables you to
+//! effortlessly capture the screen using the Graphics Capture API. This
+//! library allows you to easily capture the screen of your Windows-based
+//! computer and use it for various purposes, such as creating instructional
+//! videos, taking screenshots, or recording your gameplay.
+//!
+//! See `src/main.rs` for a full usage example.
+#![warn(clippy::nursery)]
+#![warn(clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)] // Should update as soon as possible
+
+/// Internal module for WASAPI loopback audio capture.
+mod audio;
+/// Contains the main capture functionality, including the `GraphicsCaptureApiHandler` trait and related types.
+pub mod capture;
+/// Internal module for Direct3D 11 related functionality.
+mod d3d11;
+/// Contains the encoder functionality for encoding captured frames.
+pub mod encoder;
+/// Contains the `Frame` struct and related types for representing captured frames.
+pub mod frame;
+/// Contains the types and functions related to the Graphics Capture API.
+pub mod graphics_capture_api;
+/// Contains the functionality for working with monitors and screen information.
+pub mod monitor;
+/// Contains the `Settings` struct and related types for configuring the capture settings.
+pub mod settings;
+/// Contains the functionality for working with windows and capturing specific windows.
+pub mod window;
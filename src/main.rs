@@ -1,35 +1,65 @@
 use std::{
     io::{self, Write},
-    sync::mpsc::TryRecvError,
-    thread::{self, sleep},
+    thread::sleep,
     time::{Duration, Instant},
 };
 
-use std::sync::mpsc::{self, Receiver};
-
 use windows_capture::{
-    capture::{Context, GraphicsCaptureApiHandler},
-    encoder::{AudioSettingsBuilder, ContainerSettingsBuilder, VideoEncoder, VideoSettingsBuilder},
-    frame::Frame,
+    capture::{Context, GraphicsCaptureApiHandler, MultiSource},
+    encoder::{
+        AudioSettingsBuilder, ContainerSettingsBuilder, OverflowPolicy, ScaleMode, SessionClock,
+        VideoEncoder, VideoSettingsBuilder,
+    },
+    frame::{AudioFrame, Frame},
     graphics_capture_api::InternalCaptureControl,
     monitor::Monitor,
-    settings::{ColorFormat, CursorCaptureSettings, DrawBorderSettings, Settings},
+    settings::{
+        AudioCaptureMode, ColorFormat, CursorCaptureSettings, DrawBorderSettings, Settings,
+        WinRtInitMode,
+    },
 };
 
+// Output resolution we want the recording to end up at, regardless of the
+// resolution of the monitor/window being captured. The encoder letterboxes
+// or crops on the GPU to get there (see `ScaleMode::Fit` below).
+const OUTPUT_WIDTH: u32 = 1920;
+const OUTPUT_HEIGHT: u32 = 1080;
+
+// How many frames the capture callback may hand off to the encoder thread
+// before `send_frame` starts applying `ENCODER_OVERFLOW_POLICY`.
+const ENCODER_QUEUE_DEPTH: usize = 64;
+const ENCODER_OVERFLOW_POLICY: OverflowPolicy = OverflowPolicy::DropOldest;
+
+// System audio (WASAPI loopback on the render endpoint) muxed alongside the
+// video track.
+const AUDIO_SAMPLE_RATE: u32 = 48_000;
+const AUDIO_CHANNEL_COUNT: u32 = 2;
+const AUDIO_BITRATE: u32 = 192_000;
+
 #[derive(Debug)]
 struct CaptureContext {
     name: String,
     width: u32,
     height: u32,
-    rx: Receiver<()>,
+    // Shared across every monitor in the session so the console output
+    // reports a consistent elapsed time.
+    clock: Instant,
+    // Shared across every monitor's encoder so their outputs stay
+    // frame-synchronized instead of each starting its own timeline from
+    // whichever frame it happens to see first.
+    video_clock: SessionClock,
 }
 // Handles capture events.
 struct Capture {
+    // The resolution of the monitor being captured, logged alongside the
+    // encoder's fixed output resolution so it's clear when the two differ
+    // and `ScaleMode::Fit` is doing work.
+    width: u32,
+    height: u32,
     // The video encoder that will be used to encode the frames.
     encoder: Option<VideoEncoder>,
     // To measure the time the capture has been running
     start: Instant,
-    rx: Receiver<()>,
 }
 
 impl GraphicsCaptureApiHandler for Capture {
@@ -43,94 +73,159 @@ impl GraphicsCaptureApiHandler for Capture {
     fn new(ctx: Context<Self::Flags>) -> Result<Self, Self::Error> {
         println!("Created with Flags: {:?}", ctx.flags);
 
-        let encoder = VideoEncoder::new(
-            VideoSettingsBuilder::new(ctx.flags.width, ctx.flags.height)
-                .sub_type(windows_capture::encoder::VideoSettingsSubType::H264),
-            AudioSettingsBuilder::default().disabled(true),
+        // The source (`ctx.flags.width/height`) and the output size no longer
+        // need to match: the encoder scales on the GPU, falling back to a
+        // straight copy whenever they already agree.
+        //
+        // `send_frame` below just hands the frame to a bounded queue drained
+        // by the encoder's own thread, so a slow Media Foundation sink
+        // writer can no longer stall the Graphics Capture delivery thread.
+        let mut encoder = VideoEncoder::new(
+            VideoSettingsBuilder::new(OUTPUT_WIDTH, OUTPUT_HEIGHT)
+                .sub_type(windows_capture::encoder::VideoSettingsSubType::H264)
+                .scale_mode(ScaleMode::Fit)
+                .queue_depth(ENCODER_QUEUE_DEPTH)
+                .overflow_policy(ENCODER_OVERFLOW_POLICY),
+            AudioSettingsBuilder::default()
+                .sample_rate(AUDIO_SAMPLE_RATE)
+                .channel_count(AUDIO_CHANNEL_COUNT)
+                .bitrate(AUDIO_BITRATE),
             ContainerSettingsBuilder::default(),
             ctx.flags.name,
         )?;
+        encoder.set_session_clock(ctx.flags.video_clock);
 
         Ok(Self {
+            width: ctx.flags.width,
+            height: ctx.flags.height,
             encoder: Some(encoder),
-            start: Instant::now(),
-            rx: ctx.flags.rx,
+            start: ctx.flags.clock,
         })
     }
 
-    // Called every time a new frame is available.
+    // Called every time a new frame is available. Stopping is now driven
+    // from the outside through `CaptureControl::stop`, which closes the
+    // capture item and routes us through `on_closed` below, so this no
+    // longer needs to poll anything itself.
     fn on_frame_arrived(
         &mut self,
         frame: &mut Frame,
-        capture_control: InternalCaptureControl,
+        _capture_control: InternalCaptureControl,
     ) -> Result<(), Self::Error> {
         print!(
-            "\rRecording for: {} seconds",
-            self.start.elapsed().as_secs()
+            "\rRecording {}x{} for: {} seconds (dropped frames: {})",
+            self.width,
+            self.height,
+            self.start.elapsed().as_secs(),
+            self.encoder.as_ref().unwrap().dropped_frames(),
         );
         io::stdout().flush()?;
 
         // Send the frame to the video encoder
         self.encoder.as_mut().unwrap().send_frame(frame)?;
 
-        // check if rx is disconnected
-        match self.rx.try_recv() {
-            Ok(_) | Err(TryRecvError::Disconnected) => {
-                // Finish the encoder and save the video.
-                self.encoder.take().unwrap().finish()?;
-
-                capture_control.stop();
+        Ok(())
+    }
 
-                println!();
-            }
-            Err(TryRecvError::Empty) => (),
-        };
+    // Called every time a new block of system/loopback audio is available.
+    // The frame's presentation timestamp is already aligned to the video
+    // clock, so it can be forwarded to the encoder as-is.
+    fn on_audio_frame(&mut self, frame: &mut AudioFrame) -> Result<(), Self::Error> {
+        self.encoder.as_mut().unwrap().send_audio_frame(frame)?;
 
         Ok(())
     }
 
-    // Optional handler called when the capture item (usually a window) closes.
+    // Called when the capture item closes, including in response to
+    // `CaptureControl::stop`. Drains the queue on the encoder thread before
+    // returning.
     fn on_closed(&mut self) -> Result<(), Self::Error> {
-        println!("Capture session ended");
+        let encoder = self.encoder.take().unwrap();
+        let dropped_frames = encoder.dropped_frames();
+        encoder.finish()?;
+
+        println!("\nCapture session ended");
+        if dropped_frames > 0 {
+            println!("Warning: dropped {dropped_frames} frame(s) due to encoder backpressure");
+        }
 
         Ok(())
     }
 }
 
 fn main() {
-    // Gets the foreground window, refer to the docs for other capture items
-    let primary_monitor = Monitor::primary().expect("There is no primary monitor");
-
-    let (tx, rx) = mpsc::channel::<()>();
-    let settings = Settings::new(
-        // Item to capture
-        primary_monitor,
-        // Capture cursor settings
-        CursorCaptureSettings::WithCursor,
-        // Draw border settings
-        DrawBorderSettings::WithoutBorder,
-        // The desired color format for the captured frame.
-        ColorFormat::Bgra8,
-        // Additional flags for the capture settings that will be passed to user defined `new` function.
-        CaptureContext {
-            name: "video.mp4".to_string(),
-            width: primary_monitor.width().unwrap(),
-            height: primary_monitor.height().unwrap(),
-            rx,
-        },
-    );
-
-    let recorder_thread = thread::spawn(move || {
-        // Starts the capture and takes control of the current thread.
-        // The errors from handler trait will end up here
-        match Capture::start(settings) {
-            Ok(_) => println!("Capture ended successfully"),
-            Err(e) => eprintln!("Error: {}", e),
-        }
-    });
+    // Record every connected monitor at once instead of just the primary
+    // one, as a single recording session, via `MultiSource`; refer to the
+    // docs for capturing a mix of monitors and windows instead.
+    let monitors = Monitor::enumerate().expect("Failed to enumerate monitors");
+
+    // One shared origin for every monitor's encoder, so the per-monitor
+    // outputs stay frame-synchronized and can be started/stopped together.
+    let clock = Instant::now();
+
+    // Fixed to the first monitor's first captured frame, and shared by every
+    // other monitor's encoder so all of their outputs agree on time zero
+    // instead of each starting its own timeline whenever its own first frame
+    // happens to arrive.
+    let video_clock = SessionClock::new();
+
+    let settings: Vec<_> = monitors
+        .into_iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            Settings::new(
+                // Item to capture
+                monitor,
+                // Capture cursor settings
+                CursorCaptureSettings::WithCursor,
+                // Draw border settings
+                DrawBorderSettings::WithoutBorder,
+                // The desired color format for the captured frame.
+                ColorFormat::Bgra8,
+                // Whether `start_free_threaded` should initialize the WinRT/COM
+                // apartment on the recorder thread before capturing. `Auto` attempts
+                // the init and treats "already initialized" / `RPC_E_CHANGED_MODE`
+                // as success rather than failure, so this is safe to call from a
+                // thread a host application (e.g. Tauri) already spun up. Use
+                // `Skip` instead if the caller manages COM/WinRT itself.
+                WinRtInitMode::Auto,
+                // Additional flags for the capture settings that will be passed to user defined `new` function.
+                CaptureContext {
+                    name: format!("video-{index}.mp4"),
+                    width: monitor.width().unwrap(),
+                    height: monitor.height().unwrap(),
+                    clock,
+                    video_clock: video_clock.clone(),
+                },
+            )
+            // Windows Graphics Capture needs its own frame pool per captured
+            // item, so each monitor still gets its own video session, but
+            // system/loopback audio doesn't: every session shares the single
+            // loopback thread `MultiSource` spins up, instead of each
+            // recording (and muxing) the same system audio independently.
+            .with_audio_capture(AudioCaptureMode::Shared)
+        })
+        .collect();
+
+    // Starts one capture session per monitor plus a single shared audio
+    // thread feeding all of them, and hands back a `MultiSource` we can
+    // drive from here (or stash in Tauri's `invoke_handler` state) instead
+    // of blocking the caller.
+    let multi_source =
+        MultiSource::<Capture>::start(settings).expect("Failed to start multi-monitor capture");
+
+    sleep(Duration::from_secs(30));
+
+    // Pausing holds the media timeline in place so resuming later leaves no
+    // gap in the output. Every monitor's recording is paused/resumed/stopped
+    // together since they share one `MultiSource` recording session.
+    multi_source.pause().expect("Failed to pause capture");
+    println!("\nPaused");
+    sleep(Duration::from_secs(5));
 
-    sleep(Duration::from_secs(60);
-    tx.send(()).unwrap();
+    multi_source.resume().expect("Failed to resume capture");
+    println!("Resumed");
+    sleep(Duration::from_secs(25));
 
-    let _ = recorder_thread.join();
+    multi_source.stop().expect("Failed to stop capture");
 }
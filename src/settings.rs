@@ -0,0 +1,205 @@
+use windows::Graphics::Capture::GraphicsCaptureItem;
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ColorFormat {
+    Rgba16F = 10,
+    Rgba8 = 28,
+    Bgra8 = 87,
+}
+
+impl Default for ColorFormat {
+    #[must_use]
+    #[inline]
+    fn default() -> Self {
+        Self::Rgba8
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum CursorCaptureSettings {
+    Default,
+    WithCursor,
+    WithoutCursor,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum DrawBorderSettings {
+    Default,
+    WithBorder,
+    WithoutBorder,
+}
+
+/// Controls how `start`/`start_free_threaded` initialize the WinRT/COM apartment on the
+/// thread that runs the capture.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum WinRtInitMode {
+    /// Initialize the apartment as multi-threaded, treating "already initialized" and
+    /// `RPC_E_CHANGED_MODE` (the thread already has an apartment of a different type, set up
+    /// by the host application) as success rather than failure. This is the safe default for
+    /// `start_free_threaded`, since the spawned thread is ours, but it also makes `start` safe
+    /// to call from a thread a host has already initialized, e.g. an embedder's UI thread.
+    Auto,
+    /// Don't touch the apartment at all; the caller is responsible for initializing WinRT/COM
+    /// on the capture thread themselves before calling `start`/`start_free_threaded`.
+    Skip,
+}
+
+/// Controls whether a capture session opens its own WASAPI loopback audio thread.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub enum AudioCaptureMode {
+    /// Spawn a dedicated loopback audio thread for this session and feed its output to
+    /// `on_audio_frame`, same as if no `AudioCaptureMode` existed. What every session
+    /// wants when run standalone.
+    #[default]
+    Owned,
+    /// Don't spawn an audio thread; `on_audio_frame` is fed from elsewhere instead. Used
+    /// by `crate::capture::MultiSource` so that several simultaneous sessions (e.g. one
+    /// per monitor) share a single loopback capture instead of each opening, and muxing,
+    /// the same system audio independently.
+    Shared,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+/// Represents the settings for screen capturing.
+pub struct Settings<Flags, T: TryInto<GraphicsCaptureItem>> {
+    /// The graphics capture item to capture.
+    pub(crate) item: T,
+    /// Specifies whether to capture the cursor.
+    pub(crate) cursor_capture: CursorCaptureSettings,
+    /// Specifies whether to draw a border around the captured region.
+    pub(crate) draw_border: DrawBorderSettings,
+    /// The color format for the captured graphics.
+    pub(crate) color_format: ColorFormat,
+    /// Controls how the capture thread initializes the WinRT/COM apartment.
+    pub(crate) winrt_init_mode: WinRtInitMode,
+    /// Controls whether this session opens its own loopback audio thread.
+    pub(crate) audio_capture: AudioCaptureMode,
+    /// Additional flags for capturing graphics.
+    pub(crate) flags: Flags,
+}
+
+impl<Flags, T: TryInto<GraphicsCaptureItem>> Settings<Flags, T> {
+    /// Create Capture Settings
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The graphics capture item.
+    /// * `capture_cursor` - Whether to capture the cursor or not.
+    /// * `draw_border` - Whether to draw a border around the captured region or not.
+    /// * `color_format` - The desired color format for the captured frame.
+    /// * `winrt_init_mode` - Controls how the capture thread initializes the WinRT/COM apartment.
+    /// * `flags` - Additional flags for the capture settings that will be passed to user defined `new` function.
+    ///
+    /// Starts with `AudioCaptureMode::Owned`; use `with_audio_capture` to opt a session out
+    /// of its own loopback audio thread, e.g. when driving it through `MultiSource`.
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        item: T,
+        cursor_capture: CursorCaptureSettings,
+        draw_border: DrawBorderSettings,
+        color_format: ColorFormat,
+        winrt_init_mode: WinRtInitMode,
+        flags: Flags,
+    ) -> Self {
+        Self {
+            item,
+            cursor_capture,
+            draw_border,
+            color_format,
+            winrt_init_mode,
+            audio_capture: AudioCaptureMode::Owned,
+            flags,
+        }
+    }
+
+    /// Sets whether this session opens its own loopback audio thread. Defaults to
+    /// `AudioCaptureMode::Owned`.
+    ///
+    /// # Arguments
+    ///
+    /// * `audio_capture` - The audio capture mode to use.
+    #[must_use]
+    #[inline]
+    pub const fn with_audio_capture(mut self, audio_capture: AudioCaptureMode) -> Self {
+        self.audio_capture = audio_capture;
+        self
+    }
+
+    /// Get the item
+    ///
+    /// # Returns
+    ///
+    /// The item to be captured
+    #[must_use]
+    #[inline]
+    pub const fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Get the cursor capture settings
+    ///
+    /// # Returns
+    ///
+    /// The cursor capture settings
+    #[must_use]
+    #[inline]
+    pub const fn cursor_capture(&self) -> CursorCaptureSettings {
+        self.cursor_capture
+    }
+
+    /// Get the draw border settings
+    ///
+    /// # Returns
+    ///
+    /// The draw border settings
+    #[must_use]
+    #[inline]
+    pub const fn draw_border(&self) -> DrawBorderSettings {
+        self.draw_border
+    }
+
+    /// Get the color format
+    ///
+    /// # Returns
+    ///
+    /// The color format
+    #[must_use]
+    #[inline]
+    pub const fn color_format(&self) -> ColorFormat {
+        self.color_format
+    }
+
+    /// Get the WinRT apartment initialization mode
+    ///
+    /// # Returns
+    ///
+    /// The WinRT apartment initialization mode
+    #[must_use]
+    #[inline]
+    pub const fn winrt_init_mode(&self) -> WinRtInitMode {
+        self.winrt_init_mode
+    }
+
+    /// Get the audio capture mode
+    ///
+    /// # Returns
+    ///
+    /// The audio capture mode
+    #[must_use]
+    #[inline]
+    pub const fn audio_capture(&self) -> AudioCaptureMode {
+        self.audio_capture
+    }
+
+    /// Get the flags
+    ///
+    /// # Returns
+    ///
+    /// The flags
+    #[must_use]
+    #[inline]
+    pub const fn flags(&self) -> &Flags {
+        &self.flags
+    }
+}